@@ -1,8 +1,19 @@
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub(crate) struct Position {
-    pub(crate) offset: usize,
-    pub(crate) line: usize,
-    pub(crate) col: usize,
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub col: usize,
 }
 
-mod stream;
\ No newline at end of file
+/// The start and end `Position` a token covers, so callers can point at the
+/// exact offending text instead of just the token's start.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+mod scanner;
+mod stream;
+
+pub use scanner::{Lexeme, Scanner, Token, TokenType};
\ No newline at end of file
@@ -1,19 +1,30 @@
-use super::{Position, stream::Stream};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use super::{Position, Span, stream::Stream};
 
 #[derive(Debug, PartialEq)]
-pub(crate) struct Token<'a> {
-    pos: Position,
+pub struct Token<'a> {
+    span: Span,
     content: TokenType<'a>,
 }
 
 impl<'a> Token<'a> {
-    fn new(pos: Position, content: TokenType<'a>) -> Self {
-        Self { pos, content }
+    fn new(span: Span, content: TokenType<'a>) -> Self {
+        Self { span, content }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn content(&self) -> &TokenType<'a> {
+        &self.content
     }
 }
 
 #[derive(Debug, PartialEq)]
-pub(crate) enum TokenType<'a> {
+pub enum TokenType<'a> {
     Spaces(usize),
     QuestionMark,
     Dash,
@@ -25,92 +36,988 @@ pub(crate) enum TokenType<'a> {
     RightSqBraket,
     DoubleQuote,
     SingleQuote,
-    // BlockLiteralSign,
-    // BlockJoinSigns,
-    String(&'a str),
+    String(Cow<'a, str>),
+    /// A character the scanner has no structural or scalar meaning for, and
+    /// the position it was found at.
+    Unknown(char, Position),
+    /// A recoverable lexical problem, either on its own (a tab used for
+    /// indentation) or right after the token it applies to (an unterminated
+    /// string, following the `String` token that was still emitted). Lexing
+    /// continues after this; it is not fatal. `position` is where the
+    /// problem was found, which may differ from the span of the token it
+    /// applies to.
+    Error {
+        message: &'static str,
+        position: Position,
+    },
 }
 
-pub(crate) struct Scanner<'a> {
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BlockStyle {
+    Literal,
+    Folded,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Chomp {
+    Strip,
+    Clip,
+    Keep,
+}
+
+/// The result of a scan step in incremental mode: either a complete token,
+/// or a signal that the scanner ran out of currently available input in the
+/// middle of one (e.g. a multi-space indent, a plain or quoted scalar, or a
+/// block scalar)
+/// and needs more before it can be finished.
+#[derive(Debug, PartialEq)]
+pub enum Lexeme<'a> {
+    Token(Token<'a>),
+    Incomplete,
+}
+
+pub struct Scanner<'a> {
     data: &'a str,
     stream: Stream<'a>,
-    peeked_char: Option<char>,
+    // Chars that were read from `stream` to decide where a token ends but
+    // that belong to the *next* token, e.g. the colon in "key: value" once
+    // we've confirmed it is followed by a space. Popped before reading from
+    // `stream` again, so order of unread() calls matters (LIFO).
+    pending: Vec<(char, Position)>,
+    // Extra tokens produced while scanning a token that was already
+    // returned, e.g. the "unterminated string" error that goes with the
+    // String token it applies to. Drained before scanning resumes so a
+    // single scan step can report more than one finding.
+    queued: VecDeque<Token<'a>>,
+    // Tokens already produced by `advance` but not yet handed out through
+    // `Iterator::next`, so `peek`/`peek_n` can look ahead without losing
+    // them.
+    lookahead: VecDeque<Token<'a>>,
     in_double_quote: bool,
     in_single_quote: bool,
+    // Number of leading spaces on the line currently being scanned, i.e.
+    // what `create_indent` last reported, reset to 0 on every `Newline`.
+    // Block scalars use this (not the column their `|`/`>` indicator is
+    // found at) as the reference indentation their content must exceed,
+    // since the indicator itself is usually well past the key that owns it.
+    line_indent: usize,
+    // Whether more input may still arrive via `feed`. When true, a scan
+    // step that runs out of currently available characters in the middle
+    // of an indent run, a plain or quoted scalar or a block scalar, or at
+    // the end of available input between tokens, rewinds whatever
+    // it had already consumed and reports `Lexeme::Incomplete` instead of
+    // treating the current end of input as final.
+    partial: bool,
+    // Set by a scan helper right before it bails out for lack of input, so
+    // `next_partial` can tell "incomplete" apart from "no more tokens" even
+    // though both come back from `advance` as `None`.
+    incomplete: bool,
 }
 
 impl<'a> Scanner<'a> {
-    pub(crate) fn new(data: &'a str) -> Self {
-        let mut result = Self {
+    pub fn new(data: &'a str) -> Self {
+        Self {
             data,
             stream: Stream::new(data),
-            peeked_char: None,
+            pending: Vec::new(),
+            queued: VecDeque::new(),
+            lookahead: VecDeque::new(),
             in_double_quote: false,
             in_single_quote: false,
-        };
+            line_indent: 0,
+            partial: false,
+            incomplete: false,
+        }
+    }
 
-        result
+    /// Like `new`, but for input that may not be complete yet: `feed` can
+    /// append more of it later, and a scan step that runs out of currently
+    /// available characters mid-token reports `Lexeme::Incomplete` via
+    /// `next_partial` rather than finalizing the token early.
+    pub fn new_partial(data: &'a str) -> Self {
+        Self {
+            partial: true,
+            ..Self::new(data)
+        }
     }
 
-    pub(crate) fn next(&mut self) -> Option<Token> {
-        let (c, pos) = match self.peeked_char {
-            Some(cc) => (self.peeked_char.take(), self.stream.get_position()),
-            None => {
-                let cc = self.stream.next();
-                (cc, self.stream.get_position())
+    /// Points the scanner at `data`, a slice that starts with the same bytes
+    /// as the one the scanner was created or last fed with (usually a fresh,
+    /// longer copy) followed by bytes appended since. Already-emitted tokens
+    /// keep borrowing whatever slice was current when they were produced,
+    /// so every slice passed to `new_partial`/`feed` stays borrowed for the
+    /// scanner's whole lifetime `'a`.
+    pub fn feed(&mut self, data: &'a str) {
+        self.data = data;
+        self.stream.feed(data);
+    }
+
+    /// Signals that no more input will ever arrive. A token still in
+    /// progress the next time `next_partial` runs is finalized using
+    /// whatever was collected so far instead of reporting `Incomplete`
+    /// again.
+    pub fn finish(&mut self) {
+        self.partial = false;
+    }
+
+    /// Scans the next token without consuming more input than is currently
+    /// available. Returns `None` once scanning is genuinely done (no more
+    /// tokens, ever); returns `Some(Lexeme::Incomplete)` when a token is
+    /// still being assembled and `feed` needs to supply more bytes before
+    /// it can be completed.
+    pub fn next_partial(&mut self) -> Option<Lexeme<'a>> {
+        self.incomplete = false;
+        match self.advance() {
+            Some(token) => Some(Lexeme::Token(token)),
+            None if self.incomplete => Some(Lexeme::Incomplete),
+            None => None,
+        }
+    }
+
+    fn mark_incomplete(&mut self) {
+        self.incomplete = true;
+    }
+
+    /// Returns the next token without consuming it.
+    pub fn peek(&mut self) -> Option<&Token<'a>> {
+        self.peek_n(0)
+    }
+
+    /// Returns the `n`th upcoming token (`peek_n(0)` is the same as
+    /// `peek()`) without consuming any of them, buffering whatever tokens it
+    /// had to scan ahead of `n` in `lookahead`.
+    pub fn peek_n(&mut self, n: usize) -> Option<&Token<'a>> {
+        while self.lookahead.len() <= n {
+            match self.advance() {
+                Some(token) => self.lookahead.push_back(token),
+                None => break,
             }
+        }
+
+        self.lookahead.get(n)
+    }
+
+    /// Scans and returns the next token, bypassing the `lookahead` buffer.
+    /// `Iterator::next` drains `lookahead` first so tokens already returned
+    /// by `peek`/`peek_n` aren't scanned twice.
+    fn advance(&mut self) -> Option<Token<'a>> {
+        if let Some(token) = self.queued.pop_front() {
+            return Some(token);
+        }
+
+        let (c, pos) = match self.read_char() {
+            Some((cc, pos)) => (Some(cc), pos),
+            None => (None, self.stream.get_position()),
         };
 
         let in_quote = self.in_double_quote || self.in_single_quote;
 
+        // A space at value position (col 1 is indentation, handled by
+        // `create_indent` below) is just a separator before whatever comes
+        // next, not the start of a plain scalar: skip it so the value's
+        // token starts where the value actually does, and so a `|`/`>`
+        // right after it is recognized as a block scalar indicator.
+        let (c, pos) = match c {
+            Some(' ') if pos.col != 1 && !in_quote => match self.skip_value_spaces(pos) {
+                Some((cc, cpos)) => (Some(cc), cpos),
+                None => (None, self.stream.get_position()),
+            },
+            other => (other, pos),
+        };
+
         match c {
-            Some(cc) => match cc {
-                ' ' if pos.col == 1 => self.create_indent(pos),
-                '"' if !in_quote => {
-                    self.in_double_quote = true;
-                    self.create_token(pos, TokenType::DoubleQuote)
-                }
-                '"' if self.in_double_quote => {
-                    self.in_double_quote = false;
-                    self.create_token(pos, TokenType::DoubleQuote)
-                }
-                '\'' if !in_quote => {
-                    self.in_single_quote = true;
-                    self.create_token(pos, TokenType::SingleQuote)
-                }
-                '\'' if self.in_single_quote => {
-                    self.in_single_quote = false;
-                    self.create_token(pos, TokenType::SingleQuote)
-                }
-                ':' if !in_quote => self.create_token(pos, TokenType::Colon),
-                '?' if !in_quote => self.create_token(pos, TokenType::QuestionMark),
-                '-' if !in_quote => self.create_token(pos, TokenType::Dash),
-                '{' if !in_quote => self.create_token(pos, TokenType::LeftBraket),
-                '}' if !in_quote => self.create_token(pos, TokenType::RightBraket),
-                '[' if !in_quote => self.create_token(pos, TokenType::LeftSqBraket),
-                ']' if !in_quote => self.create_token(pos, TokenType::RightSqBraket),
-                '\n' => self.create_token(pos, TokenType::Newline),
-                _ => None, //TODO: Continue here
+            Some(cc) => {
+                let span = Span {
+                    start: pos,
+                    end: Self::position_after(pos, cc),
+                };
+
+                match cc {
+                    ' ' if pos.col == 1 => self.create_indent(pos),
+                    '\t' if pos.col == 1 && !in_quote => self.create_token(
+                        span,
+                        TokenType::Error {
+                            message: "tab used for indentation",
+                            position: pos,
+                        },
+                    ),
+                    '"' if !in_quote => {
+                        self.in_double_quote = true;
+                        self.create_token(span, TokenType::DoubleQuote)
+                    }
+                    '"' if self.in_double_quote => {
+                        self.in_double_quote = false;
+                        self.create_token(span, TokenType::DoubleQuote)
+                    }
+                    '\'' if !in_quote => {
+                        self.in_single_quote = true;
+                        self.create_token(span, TokenType::SingleQuote)
+                    }
+                    '\'' if self.in_single_quote => {
+                        self.in_single_quote = false;
+                        self.create_token(span, TokenType::SingleQuote)
+                    }
+                    ':' if !in_quote => self.create_token(span, TokenType::Colon),
+                    '?' if !in_quote => {
+                        self.scan_indicator(pos, cc, span, || TokenType::QuestionMark)
+                    }
+                    '-' if !in_quote => self.scan_indicator(pos, cc, span, || TokenType::Dash),
+                    '|' if !in_quote => self.scan_block_scalar(pos, cc, BlockStyle::Literal),
+                    '>' if !in_quote => self.scan_block_scalar(pos, cc, BlockStyle::Folded),
+                    '{' if !in_quote => self.create_token(span, TokenType::LeftBraket),
+                    '}' if !in_quote => self.create_token(span, TokenType::RightBraket),
+                    '[' if !in_quote => self.create_token(span, TokenType::LeftSqBraket),
+                    ']' if !in_quote => self.create_token(span, TokenType::RightSqBraket),
+                    '\n' => {
+                        self.line_indent = 0;
+                        self.create_token(span, TokenType::Newline)
+                    }
+                    _ if Self::is_invalid_control(cc) => {
+                        self.create_token(span, TokenType::Unknown(cc, pos))
+                    }
+                    _ if self.in_double_quote => self.scan_double_quoted(cc, pos),
+                    _ if self.in_single_quote => self.scan_single_quoted(cc, pos),
+                    _ => self.scan_plain(cc, pos),
+                }
+            }
+            _ => {
+                if self.partial {
+                    self.mark_incomplete();
+                }
+                None
+            }
+        }
+    }
+
+    /// Control characters other than the ones the scanner already treats
+    /// specially (`\n` for line breaks, `\t` for indentation/content) have
+    /// no place in a YAML document and are reported as `Unknown` rather
+    /// than silently swallowed into a scalar.
+    fn is_invalid_control(c: char) -> bool {
+        c.is_control() && c != '\n' && c != '\t'
+    }
+
+    /// `-`/`?` are YAML's block-sequence-entry and explicit-key indicators
+    /// only when followed by a space, a line break, or the end of input;
+    /// otherwise `sign` just starts a plain scalar instead, e.g. the `-` in
+    /// `-5`. `make` builds the indicator's token once that's confirmed.
+    fn scan_indicator(
+        &mut self,
+        pos: Position,
+        sign: char,
+        span: Span,
+        make: impl FnOnce() -> TokenType<'a>,
+    ) -> Option<Token<'a>> {
+        match self.read_char() {
+            Some((c, cpos)) if c == ' ' || c == '\n' => {
+                self.unread(c, cpos);
+                self.create_token(span, make())
+            }
+            Some(pair) => {
+                self.unread(pair.0, pair.1);
+                self.scan_plain(sign, pos)
+            }
+            None if self.partial => {
+                self.unread(sign, pos);
+                self.mark_incomplete();
+                None
+            }
+            None => self.create_token(span, make()),
+        }
+    }
+
+    /// Skips a run of spaces at value position, starting with the one
+    /// already read at `first`, and returns the first non-space char found
+    /// after them. Returns `None` at genuine end of input, or (in partial
+    /// mode) rewinds everything read and reports incomplete if the spaces
+    /// run right up to currently available input, since more could still
+    /// be on the way.
+    fn skip_value_spaces(&mut self, first: Position) -> Option<(char, Position)> {
+        let mut consumed = vec![(' ', first)];
+        loop {
+            match self.read_char() {
+                Some((' ', spos)) => consumed.push((' ', spos)),
+                Some(pair) => return Some(pair),
+                None if self.partial => {
+                    self.unread_all(consumed);
+                    self.mark_incomplete();
+                    return None;
+                }
+                None => return None,
+            }
+        }
+    }
+
+    /// The position right after `c`, given `c` was read at `pos`. Chars
+    /// coming out of `read_char` are already `\r`-normalized by `Stream`, so
+    /// this only needs to account for `\n` advancing the line.
+    fn position_after(pos: Position, c: char) -> Position {
+        if c == '\n' {
+            Position {
+                offset: pos.offset + c.len_utf8(),
+                line: pos.line + 1,
+                col: 1,
+            }
+        } else {
+            Position {
+                offset: pos.offset + c.len_utf8(),
+                line: pos.line,
+                col: pos.col + 1,
+            }
+        }
+    }
+
+    fn create_indent(&mut self, pos: Position) -> Option<Token<'a>> {
+        let mut consumed = vec![(' ', pos)];
+        loop {
+            match self.read_char() {
+                Some((c, cpos)) if c == ' ' => consumed.push((c, cpos)),
+                Some((c, cpos)) => {
+                    self.unread(c, cpos);
+                    break;
+                }
+                None if self.partial => {
+                    self.unread_all(consumed);
+                    self.mark_incomplete();
+                    return None;
+                }
+                None => break,
+            }
+        }
+
+        let count = consumed.len();
+        let end = Self::end_of_raw(&consumed);
+        self.line_indent = count;
+        self.create_token(Span { start: pos, end }, TokenType::Spaces(count))
+    }
+
+    /// Scans a plain (unquoted) scalar starting at `first`, which has
+    /// already been consumed from the stream. Plain scalars run until a
+    /// newline, a flow indicator (`{`, `}`, `[`, `]`), a colon followed by a
+    /// space, an invalid control character, or the end of input.
+    fn scan_plain(&mut self, first: char, pos: Position) -> Option<Token<'a>> {
+        let start = pos.offset;
+        let mut end_pos = Self::position_after(pos, first);
+        let mut consumed = vec![(first, pos)];
+
+        loop {
+            let (c, cpos) = match self.read_char() {
+                Some(pair) => pair,
+                None if self.partial => {
+                    self.unread_all(consumed);
+                    self.mark_incomplete();
+                    return None;
+                }
+                None => break,
+            };
+            consumed.push((c, cpos));
+            match c {
+                '\n' | '{' | '}' | '[' | ']' => {
+                    self.unread(c, cpos);
+                    break;
+                }
+                _ if Self::is_invalid_control(c) => {
+                    self.unread(c, cpos);
+                    break;
+                }
+                ':' => match self.read_char() {
+                    Some((' ', spos)) => {
+                        self.unread(' ', spos);
+                        self.unread(':', cpos);
+                        break;
+                    }
+                    Some((next, npos)) => {
+                        end_pos = Self::position_after(cpos, ':');
+                        self.unread(next, npos);
+                    }
+                    None if self.partial => {
+                        self.unread_all(consumed);
+                        self.mark_incomplete();
+                        return None;
+                    }
+                    None => {
+                        end_pos = Self::position_after(cpos, ':');
+                        break;
+                    }
+                },
+                _ => {
+                    end_pos = Self::position_after(cpos, c);
+                }
+            }
+        }
+
+        self.create_token(
+            Span {
+                start: pos,
+                end: end_pos,
+            },
+            TokenType::String(Cow::Borrowed(&self.data[start..end_pos.offset])),
+        )
+    }
+
+    /// Scans the content of a double-quoted scalar, starting right after the
+    /// opening `"` (already emitted as its own token). Recognizes the
+    /// `\n`, `\t`, `\"`, `\\` and `\uXXXX` escapes; anything else behind a
+    /// backslash is kept as a literal character. Stops before the closing
+    /// `"`, leaving it to be read (and emitted) by the next `next()` call.
+    fn scan_double_quoted(&mut self, first: char, pos: Position) -> Option<Token<'a>> {
+        let start = pos.offset;
+        let mut end_pos = pos;
+        let mut owned: Option<String> = None;
+        let mut current = Some((first, pos));
+        let mut terminated = false;
+        let mut consumed = vec![(first, pos)];
+
+        loop {
+            let (c, cpos) = match current.take() {
+                Some(pair) => pair,
+                None => match self.read_char() {
+                    Some(pair) => {
+                        consumed.push(pair);
+                        pair
+                    }
+                    None => {
+                        if self.partial {
+                            self.unread_all(consumed);
+                            self.mark_incomplete();
+                            return None;
+                        }
+                        break;
+                    }
+                },
+            };
+
+            if c == '"' {
+                self.unread(c, cpos);
+                consumed.pop();
+                terminated = true;
+                break;
+            }
+
+            if Self::is_invalid_control(c) {
+                self.unread(c, cpos);
+                consumed.pop();
+                break;
+            }
+
+            if c == '\\' {
+                let (esc, epos) = match self.read_char() {
+                    Some(pair) => {
+                        consumed.push(pair);
+                        pair
+                    }
+                    None => {
+                        if self.partial {
+                            self.unread_all(consumed);
+                            self.mark_incomplete();
+                            return None;
+                        }
+                        break;
+                    }
+                };
+
+                if esc == 'u' {
+                    let mut hex = String::with_capacity(4);
+                    let mut last_pos = epos;
+                    let mut hex_incomplete = false;
+                    for _ in 0..4 {
+                        match self.read_char() {
+                            Some((hc, hpos)) if hc.is_ascii_hexdigit() => {
+                                consumed.push((hc, hpos));
+                                hex.push(hc);
+                                last_pos = hpos;
+                            }
+                            Some((hc, hpos)) => {
+                                self.unread(hc, hpos);
+                                break;
+                            }
+                            None if self.partial => {
+                                hex_incomplete = true;
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+
+                    if hex_incomplete {
+                        self.unread_all(consumed);
+                        self.mark_incomplete();
+                        return None;
+                    }
+
+                    end_pos = Self::position_after(last_pos, 'u');
+                    let buf =
+                        owned.get_or_insert_with(|| self.data[start..cpos.offset].to_string());
+                    if let Some(decoded) =
+                        u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                    {
+                        buf.push(decoded);
+                    }
+                    continue;
+                }
+
+                let resolved = match esc {
+                    'n' => '\n',
+                    't' => '\t',
+                    '"' => '"',
+                    '\\' => '\\',
+                    other => other,
+                };
+
+                end_pos = Self::position_after(epos, esc);
+                let buf = owned.get_or_insert_with(|| self.data[start..cpos.offset].to_string());
+                buf.push(resolved);
+                continue;
+            }
+
+            end_pos = Self::position_after(cpos, c);
+            if let Some(buf) = owned.as_mut() {
+                buf.push(c);
+            }
+        }
+
+        let content = match owned {
+            Some(s) => Cow::Owned(s),
+            None => Cow::Borrowed(&self.data[start..end_pos.offset]),
+        };
+
+        if !terminated {
+            self.in_double_quote = false;
+            self.queue_error(pos, "unterminated double-quoted string");
+        }
+
+        self.create_token(
+            Span {
+                start: pos,
+                end: end_pos,
+            },
+            TokenType::String(content),
+        )
+    }
+
+    /// Scans the content of a single-quoted scalar, starting right after the
+    /// opening `'`. A doubled quote (`''`) is a literal `'`; a single quote
+    /// ends the scalar and is left unread for the next `next()` call.
+    fn scan_single_quoted(&mut self, first: char, pos: Position) -> Option<Token<'a>> {
+        let start = pos.offset;
+        let mut end_pos = pos;
+        let mut owned: Option<String> = None;
+        let mut current = Some((first, pos));
+        let mut terminated = false;
+        let mut consumed = vec![(first, pos)];
+
+        loop {
+            let (c, cpos) = match current.take() {
+                Some(pair) => pair,
+                None => match self.read_char() {
+                    Some(pair) => {
+                        consumed.push(pair);
+                        pair
+                    }
+                    None => {
+                        if self.partial {
+                            self.unread_all(consumed);
+                            self.mark_incomplete();
+                            return None;
+                        }
+                        break;
+                    }
+                },
+            };
+
+            if c == '\'' {
+                match self.read_char() {
+                    Some(('\'', npos)) => {
+                        consumed.push(('\'', npos));
+                        end_pos = Self::position_after(npos, '\'');
+                        let buf =
+                            owned.get_or_insert_with(|| self.data[start..cpos.offset].to_string());
+                        buf.push('\'');
+                        continue;
+                    }
+                    Some((other, opos)) => {
+                        self.unread(other, opos);
+                        self.unread(c, cpos);
+                        consumed.pop();
+                        terminated = true;
+                        break;
+                    }
+                    None if self.partial => {
+                        self.unread_all(consumed);
+                        self.mark_incomplete();
+                        return None;
+                    }
+                    None => {
+                        self.unread(c, cpos);
+                        consumed.pop();
+                        terminated = true;
+                        break;
+                    }
+                }
+            }
+
+            if Self::is_invalid_control(c) {
+                self.unread(c, cpos);
+                consumed.pop();
+                break;
+            }
+
+            end_pos = Self::position_after(cpos, c);
+            if let Some(buf) = owned.as_mut() {
+                buf.push(c);
+            }
+        }
+
+        let content = match owned {
+            Some(s) => Cow::Owned(s),
+            None => Cow::Borrowed(&self.data[start..end_pos.offset]),
+        };
+
+        if !terminated {
+            self.in_single_quote = false;
+            self.queue_error(pos, "unterminated single-quoted string");
+        }
+
+        self.create_token(
+            Span {
+                start: pos,
+                end: end_pos,
             },
-            _ => None,
+            TokenType::String(content),
+        )
+    }
+
+    /// Scans a block scalar header (`|`/`>` plus an optional indentation
+    /// digit and chomping indicator, in either order) and, if it's valid,
+    /// the block content that follows. `sign` is the `|`/`>` character
+    /// itself, already consumed. If what follows isn't a valid header
+    /// (anything but an indentation digit, a chomping indicator, trailing
+    /// spaces and a line break), `sign` is treated as the start of a plain
+    /// scalar instead, matching how a stray `|`/`>` reads in real YAML.
+    fn scan_block_scalar(
+        &mut self,
+        pos: Position,
+        sign: char,
+        style: BlockStyle,
+    ) -> Option<Token<'a>> {
+        let mut consumed = vec![(sign, pos)];
+        let mut indent_digit: Option<usize> = None;
+        let mut chomp_seen = false;
+        let mut chomp = Chomp::Clip;
+
+        loop {
+            match self.read_char() {
+                Some((c, cpos)) if indent_digit.is_none() && ('1'..='9').contains(&c) => {
+                    indent_digit = Some(c.to_digit(10).unwrap() as usize);
+                    consumed.push((c, cpos));
+                }
+                Some((c, cpos)) if !chomp_seen && (c == '-' || c == '+') => {
+                    chomp = if c == '-' { Chomp::Strip } else { Chomp::Keep };
+                    chomp_seen = true;
+                    consumed.push((c, cpos));
+                }
+                Some(pair) => {
+                    self.unread(pair.0, pair.1);
+                    break;
+                }
+                None if self.partial => {
+                    self.unread_all(consumed);
+                    self.mark_incomplete();
+                    return None;
+                }
+                None => break,
+            }
         }
+
+        let mut trailing = Vec::new();
+        let mut header_end = Self::position_after(pos, sign);
+        if let Some((c, cpos)) = consumed.last() {
+            header_end = Self::position_after(*cpos, *c);
+        }
+        let valid_header = loop {
+            match self.read_char() {
+                Some((' ', cpos)) => {
+                    header_end = Self::position_after(cpos, ' ');
+                    trailing.push((' ', cpos));
+                }
+                Some(('\n', cpos)) => {
+                    header_end = Self::position_after(cpos, '\n');
+                    trailing.push(('\n', cpos));
+                    break true;
+                }
+                Some(pair) => {
+                    self.unread(pair.0, pair.1);
+                    break false;
+                }
+                None if self.partial => {
+                    self.unread_all(trailing);
+                    self.unread_all(consumed);
+                    self.mark_incomplete();
+                    return None;
+                }
+                None => break true,
+            }
+        };
+
+        if !valid_header {
+            self.unread_all(trailing);
+            // `consumed` was seeded with `sign` so an incomplete header could
+            // unread it too; here `sign` is passed to `scan_plain` directly
+            // instead, so only the digit/chomp chars (if any) go back.
+            consumed.remove(0);
+            self.unread_all(consumed);
+            return self.scan_plain(sign, pos);
+        }
+
+        // The block's content must be indented more than the line the
+        // indicator itself sits on, not more than the column the indicator
+        // happens to be found at: `description: |` has its `|` column to
+        // the right of `description`, but the block's reference indentation
+        // is still `description`'s own (usually 0).
+        let parent_indent = self.line_indent;
+        let result =
+            self.collect_block_lines(pos, style, parent_indent, indent_digit, chomp, header_end);
+        if result.is_none() {
+            // `collect_block_lines` only ever returns `None` to signal that
+            // it ran out of currently available input and has already
+            // rewound its own progress; undo the header too so a retry
+            // after `feed` starts completely fresh.
+            self.unread_all(trailing);
+            self.unread_all(consumed);
+        }
+        result
     }
 
-    fn create_indent(&mut self, pos: Position) -> Option<Token> {
-        let mut count = 1;
-        while let Some(c) = self.stream.next() {
-            if c != ' ' {
-                self.peeked_char = Some(c);
+    /// Reads the rest of the current line char by char, including the
+    /// terminating `\n` if there is one (there won't be for the last line
+    /// of input). Returns `None` once there is nothing left to read at all.
+    fn read_raw_line(&mut self) -> Option<Vec<(char, Position)>> {
+        let mut chars = Vec::new();
+        while let Some((c, cpos)) = self.read_char() {
+            chars.push((c, cpos));
+            if c == '\n' {
+                break;
+            }
+        }
+
+        if chars.is_empty() { None } else { Some(chars) }
+    }
+
+    /// Pushes a run of chars back in source order, so the next `read_char()`
+    /// calls reproduce them exactly as they were read.
+    fn unread_all(&mut self, chars: Vec<(char, Position)>) {
+        for (c, p) in chars.into_iter().rev() {
+            self.unread(c, p);
+        }
+    }
+
+    /// Collects the lines making up a block scalar's content, starting
+    /// right after its header line. `parent_indent` (the indentation of the
+    /// line the `|`/`>` indicator itself sits on) is the reference
+    /// indentation: with an explicit digit, the block's own indentation is
+    /// `parent_indent + digit`; without one, it's the indentation of the
+    /// first non-empty line. Blank lines are always content and never end
+    /// the block; the first non-blank line indented less than the block's
+    /// indentation (without a digit: not more than `parent_indent`) ends it
+    /// and is left unread for the outer scanner to pick up.
+    fn collect_block_lines(
+        &mut self,
+        pos: Position,
+        style: BlockStyle,
+        parent_indent: usize,
+        indent_digit: Option<usize>,
+        chomp: Chomp,
+        mut end_pos: Position,
+    ) -> Option<Token<'a>> {
+        let mut min_leading = indent_digit.map(|d| parent_indent + d);
+        let mut pending_blanks: Vec<Vec<(char, Position)>> = Vec::new();
+        // (content with its leading `min_leading` spaces stripped, is_blank, is_more_indented)
+        let mut lines: Vec<(String, bool, bool)> = Vec::new();
+        // Every char committed to `lines`/`pending_blanks` so far, in source
+        // order, so an incomplete bailout can put all of it back at once.
+        let mut collected: Vec<(char, Position)> = Vec::new();
+
+        loop {
+            let raw = match self.read_raw_line() {
+                Some(raw) => raw,
+                None => {
+                    if self.partial {
+                        self.unread_all(collected);
+                        self.mark_incomplete();
+                        return None;
+                    }
+                    break;
+                }
+            };
+
+            let has_newline = matches!(raw.last(), Some((c, _)) if *c == '\n');
+            if !has_newline && self.partial {
+                // The last line available isn't terminated yet; more of it
+                // may still be on the way, so don't decide its fate now.
+                collected.extend(raw);
+                self.unread_all(collected);
+                self.mark_incomplete();
+                return None;
+            }
+
+            let raw_end = Self::end_of_raw(&raw);
+            let body = if has_newline { &raw[..raw.len() - 1] } else { &raw[..] };
+            let leading = body.iter().take_while(|(c, _)| *c == ' ').count();
+            let is_blank = body.iter().all(|(c, _)| *c == ' ');
+
+            if is_blank {
+                if min_leading.is_none() {
+                    pending_blanks.push(raw);
+                } else {
+                    collected.extend(raw);
+                    lines.push((String::new(), true, false));
+                    end_pos = raw_end;
+                }
+                continue;
+            }
+
+            let threshold = match min_leading {
+                Some(t) => t,
+                None if leading > parent_indent => {
+                    min_leading = Some(leading);
+                    leading
+                }
+                None => {
+                    self.unread_all(raw);
+                    for blank in pending_blanks.into_iter().rev() {
+                        self.unread_all(blank);
+                    }
+                    break;
+                }
+            };
+
+            if leading < threshold {
+                self.unread_all(raw);
                 break;
             }
 
-            count += 1;
+            for blank in pending_blanks.drain(..) {
+                collected.extend(blank);
+                lines.push((String::new(), true, false));
+            }
+
+            let is_more_indented = leading > threshold;
+            let content: String = body[threshold..].iter().map(|(c, _)| *c).collect();
+            collected.extend(raw);
+            lines.push((content, false, is_more_indented));
+            end_pos = raw_end;
         }
 
-        self.create_token(pos, TokenType::Spaces(count))
+        let joined = match style {
+            BlockStyle::Literal => {
+                let mut s = String::new();
+                for (text, _, _) in &lines {
+                    s.push_str(text);
+                    s.push('\n');
+                }
+                s
+            }
+            BlockStyle::Folded => {
+                let mut s = String::new();
+                for (i, (text, is_blank, is_more)) in lines.iter().enumerate() {
+                    s.push_str(text);
+                    if *is_blank || *is_more {
+                        // Blank and more-indented lines always keep their
+                        // own literal break; a blank line's break is what
+                        // represents it in the output, so a normal line
+                        // right before one must not add another (a run of
+                        // n blank lines folds to n breaks, not n+1).
+                        s.push('\n');
+                        continue;
+                    }
+                    match lines.get(i + 1) {
+                        None => s.push('\n'),
+                        Some((_, true, _)) => {}
+                        Some((_, false, true)) => s.push('\n'),
+                        Some((_, false, false)) => s.push(' '),
+                    }
+                }
+                s
+            }
+        };
+
+        let content = match chomp {
+            Chomp::Keep => joined,
+            Chomp::Clip => {
+                let trimmed = joined.trim_end_matches('\n');
+                if trimmed.is_empty() {
+                    String::new()
+                } else {
+                    format!("{trimmed}\n")
+                }
+            }
+            Chomp::Strip => joined.trim_end_matches('\n').to_string(),
+        };
+
+        // The line right after the block (already unread for the outer
+        // scanner) hasn't gone through `create_indent`/the `Newline` arm
+        // yet, since every newline consumed above bypassed that dispatch;
+        // reset here so it starts from a clean slate like any other line.
+        self.line_indent = 0;
+
+        self.create_token(
+            Span {
+                start: pos,
+                end: end_pos,
+            },
+            TokenType::String(Cow::Owned(content)),
+        )
+    }
+
+    /// The position right after the last char of a raw line previously
+    /// returned by `read_raw_line`.
+    fn end_of_raw(raw: &[(char, Position)]) -> Position {
+        match raw.last() {
+            Some((c, cpos)) => Self::position_after(*cpos, *c),
+            None => unreachable!("read_raw_line never returns an empty line"),
+        }
     }
 
-    fn create_token(&self, pos: Position, content: TokenType<'a>) -> Option<Token> {
-        Some(Token::new(pos, content))
+    fn create_token(&self, span: Span, content: TokenType<'a>) -> Option<Token<'a>> {
+        Some(Token::new(span, content))
+    }
+
+    fn read_char(&mut self) -> Option<(char, Position)> {
+        match self.pending.pop() {
+            Some(pair) => Some(pair),
+            None => {
+                let c = self.stream.next()?;
+                Some((c, self.stream.get_position()))
+            }
+        }
+    }
+
+    fn unread(&mut self, c: char, pos: Position) {
+        self.pending.push((c, pos));
+    }
+
+    fn queue_error(&mut self, pos: Position, message: &'static str) {
+        self.queued.push_back(Token::new(
+            Span {
+                start: pos,
+                end: pos,
+            },
+            TokenType::Error {
+                message,
+                position: pos,
+            },
+        ));
+    }
+}
+
+impl<'a> Iterator for Scanner<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        match self.lookahead.pop_front() {
+            Some(token) => Some(token),
+            None => self.advance(),
+        }
     }
 }
 
@@ -133,7 +1040,371 @@ mod test_scanner {
         let mut scanner = Scanner::new(data);
 
         let token = scanner.next();
-        assert_token(token, 0, 0, 0, TokenType::String("Hello world!"));
+        assert_token(
+            token,
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("Hello world!")),
+        );
+    }
+
+    #[test]
+    fn test_value_does_not_include_the_separating_space() {
+        let data = "key: value";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("key")),
+        );
+        assert_token(scanner.next(), 3, 1, 4, TokenType::Colon);
+        assert_token(
+            scanner.next(),
+            5,
+            1,
+            6,
+            TokenType::String(Cow::Borrowed("value")),
+        );
+    }
+
+    #[test]
+    fn test_negative_number_value_is_not_split_on_the_dash() {
+        // A bare `-`/`?` is only a block-sequence/explicit-key indicator
+        // when followed by a space or newline; right up against a digit
+        // (as in a negative number) it's just the start of the scalar.
+        let data = "offset: -5";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("offset")),
+        );
+        assert_token(scanner.next(), 6, 1, 7, TokenType::Colon);
+        assert_token(
+            scanner.next(),
+            8,
+            1,
+            9,
+            TokenType::String(Cow::Borrowed("-5")),
+        );
+    }
+
+    #[test]
+    fn test_dash_followed_by_space_is_a_sequence_entry_indicator() {
+        let data = "- foo";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(scanner.next(), 0, 1, 1, TokenType::Dash);
+        assert_token(
+            scanner.next(),
+            2,
+            1,
+            3,
+            TokenType::String(Cow::Borrowed("foo")),
+        );
+    }
+
+    #[test]
+    fn test_unterminated_double_quote() {
+        let data = "\"abc";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(scanner.next(), 0, 1, 1, TokenType::DoubleQuote);
+        assert_token(
+            scanner.next(),
+            1,
+            1,
+            2,
+            TokenType::String(Cow::Borrowed("abc")),
+        );
+        assert_token(
+            scanner.next(),
+            1,
+            1,
+            2,
+            TokenType::Error {
+                message: "unterminated double-quoted string",
+                position: Position {
+                    offset: 1,
+                    line: 1,
+                    col: 2,
+                },
+            },
+        );
+        assert!(scanner.next().is_none());
+    }
+
+    #[test]
+    fn test_control_character_is_unknown() {
+        let data = "a\u{1}b";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("a")),
+        );
+        assert_token(
+            scanner.next(),
+            1,
+            1,
+            2,
+            TokenType::Unknown(
+                '\u{1}',
+                Position {
+                    offset: 1,
+                    line: 1,
+                    col: 2,
+                },
+            ),
+        );
+        assert_token(
+            scanner.next(),
+            2,
+            1,
+            3,
+            TokenType::String(Cow::Borrowed("b")),
+        );
+    }
+
+    #[test]
+    fn test_literal_block_scalar() {
+        let data = "|\n  foo\n  bar\n";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("foo\nbar\n")),
+        );
+    }
+
+    #[test]
+    fn test_block_scalar_at_value_position() {
+        // The common OAS case: `|`/`>` right after `key: `, not alone at
+        // column 1. The block's indentation is relative to `description`'s
+        // own column (0), not the column of `|` itself.
+        let data = "description: |\n  Hello\n  World\n";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("description")),
+        );
+        assert_token(scanner.next(), 11, 1, 12, TokenType::Colon);
+        assert_token(
+            scanner.next(),
+            13,
+            1,
+            14,
+            TokenType::String(Cow::Borrowed("Hello\nWorld\n")),
+        );
+    }
+
+    #[test]
+    fn test_folded_block_scalar_keeps_break_around_blank_line() {
+        // A run of n blank lines folds to n breaks, not n+1: the break that
+        // would otherwise end "foo" is what the blank line itself supplies.
+        let data = ">\n  foo\n\n  bar\n";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("foo\nbar\n")),
+        );
+    }
+
+    #[test]
+    fn test_folded_block_scalar_two_blank_lines() {
+        let data = ">\n  foo\n\n\n  bar\n";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("foo\n\nbar\n")),
+        );
+    }
+
+    #[test]
+    fn test_block_scalar_strip_chomping_drops_trailing_blank_lines() {
+        let data = "|-\n  a\n\n\n";
+        let mut scanner = Scanner::new(data);
+
+        assert_token(scanner.next(), 0, 1, 1, TokenType::String(Cow::Borrowed("a")));
+    }
+
+    #[test]
+    fn test_indent_span_covers_every_space() {
+        let data = "   a";
+        let mut scanner = Scanner::new(data);
+
+        let token = scanner.next().unwrap();
+        assert_eq!(
+            token.span(),
+            Span {
+                start: Position {
+                    offset: 0,
+                    line: 1,
+                    col: 1
+                },
+                end: Position {
+                    offset: 3,
+                    line: 1,
+                    col: 4
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_block_scalar_span_covers_header_through_last_line() {
+        let data = "|\n  foo\n  bar\n";
+        let mut scanner = Scanner::new(data);
+
+        let token = scanner.next().unwrap();
+        assert_eq!(
+            token.span(),
+            Span {
+                start: Position {
+                    offset: 0,
+                    line: 1,
+                    col: 1
+                },
+                end: Position {
+                    offset: data.len(),
+                    line: 4,
+                    col: 1
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_peek_does_not_consume() {
+        let data = "a\nb";
+        let mut scanner = Scanner::new(data);
+
+        assert_eq!(
+            scanner.peek().unwrap().content(),
+            &TokenType::String(Cow::Borrowed("a"))
+        );
+        assert_eq!(
+            scanner.peek().unwrap().content(),
+            &TokenType::String(Cow::Borrowed("a"))
+        );
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("a")),
+        );
+    }
+
+    #[test]
+    fn test_peek_n_looks_past_the_next_token() {
+        let data = "a\nb";
+        let mut scanner = Scanner::new(data);
+
+        assert_eq!(
+            scanner.peek_n(1).unwrap().content(),
+            &TokenType::Newline
+        );
+        // peek_n must not have skipped the token ahead of it.
+        assert_token(
+            scanner.next(),
+            0,
+            1,
+            1,
+            TokenType::String(Cow::Borrowed("a")),
+        );
+        assert_token(scanner.next(), 1, 1, 2, TokenType::Newline);
+    }
+
+    #[test]
+    fn test_scanner_as_iterator() {
+        let data = "a\nb";
+        let scanner = Scanner::new(data);
+
+        let contents: Vec<TokenType> = scanner.map(|t| t.content).collect();
+        assert_eq!(
+            contents,
+            vec![
+                TokenType::String(Cow::Borrowed("a")),
+                TokenType::Newline,
+                TokenType::String(Cow::Borrowed("b")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_partial_reports_incomplete_mid_indent() {
+        let mut scanner = Scanner::new_partial("  ");
+        assert_eq!(scanner.next_partial(), Some(Lexeme::Incomplete));
+
+        let grown = format!("{}  a", "  ");
+        scanner.feed(&grown);
+        match scanner.next_partial() {
+            Some(Lexeme::Token(token)) => {
+                assert_eq!(token.content, TokenType::Spaces(4));
+            }
+            other => panic!("expected a complete Spaces token, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_next_partial_reports_incomplete_mid_quoted_scalar() {
+        let mut scanner = Scanner::new_partial("\"abc");
+        assert_token(scanner.next(), 0, 1, 1, TokenType::DoubleQuote);
+        assert_eq!(scanner.next_partial(), Some(Lexeme::Incomplete));
+
+        let grown = "\"abc\" ".to_string();
+        scanner.feed(&grown);
+        match scanner.next_partial() {
+            Some(Lexeme::Token(token)) => {
+                assert_eq!(token.content, TokenType::String(Cow::Borrowed("abc")));
+            }
+            other => panic!("expected the completed string, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_next_partial_reports_incomplete_mid_block_scalar() {
+        let mut scanner = Scanner::new_partial("|\n  foo\n  ba");
+        assert_eq!(scanner.next_partial(), Some(Lexeme::Incomplete));
+
+        let grown = "|\n  foo\n  bar\n".to_string();
+        scanner.feed(&grown);
+        scanner.finish();
+        match scanner.next_partial() {
+            Some(Lexeme::Token(token)) => {
+                assert_eq!(
+                    token.content,
+                    TokenType::String(Cow::Borrowed("foo\nbar\n"))
+                );
+            }
+            other => panic!("expected the completed block scalar, got {other:?}"),
+        }
     }
 
     fn assert_token(
@@ -146,12 +1417,13 @@ mod test_scanner {
         assert!(token.is_some());
         let token = token.unwrap();
         assert_eq!(
-            token,
-            Token {
-                pos: Position { offset, col, line },
-                content
-            },
-            "Token does not match given position or content!"
+            token.span().start,
+            Position { offset, col, line },
+            "Token does not start where expected!"
+        );
+        assert_eq!(
+            token.content, content,
+            "Token does not match given content!"
         );
     }
 }
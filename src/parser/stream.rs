@@ -3,7 +3,15 @@ use std::str::CharIndices;
 use super::Position;
 
 pub(crate) struct Stream<'a> {
+    // The slice `char_indices` was built from, kept around only so `feed`
+    // knows how many bytes of it were already available (its full length)
+    // when pointing a fresh `CharIndices` at the newly appended data.
+    data: &'a str,
     char_indices: CharIndices<'a>,
+    // Offset into `data` that `char_indices` starts at, since `feed` points
+    // `char_indices` at `data[base..]` rather than `data` as a whole;
+    // `update_pos` adds this back to recover an absolute offset.
+    base: usize,
     col: usize,
     offset: usize,
     line: usize,
@@ -14,7 +22,9 @@ pub(crate) struct Stream<'a> {
 impl<'a> Stream<'a> {
     pub(crate) fn new(data: &'a str) -> Self {
         Self {
+            data,
             char_indices: data.char_indices(),
+            base: 0,
             col: 0,
             offset: 0,
             line: 1,
@@ -23,6 +33,19 @@ impl<'a> Stream<'a> {
         }
     }
 
+    /// Re-points the stream at `data`, a slice containing the same bytes
+    /// already consumed plus more appended after them (e.g. the caller read
+    /// further into a streamed document), and resumes right after the last
+    /// character already consumed instead of starting over. `offset`/`line`/
+    /// `col` carry forward unchanged; only valid to call once `next()` has
+    /// returned `None`, since that's the only time `peek` is guaranteed
+    /// empty and `char_indices` guaranteed fully drained.
+    pub(crate) fn feed(&mut self, data: &'a str) {
+        self.base = self.data.len();
+        self.data = data;
+        self.char_indices = data[self.base..].char_indices();
+    }
+
     pub(crate) fn get_position(&self) -> Position {
         Position {
             offset: self.offset,
@@ -73,7 +96,7 @@ impl<'a> Stream<'a> {
     }
 
     fn update_pos(&mut self, offset: usize) {
-        self.offset = offset;
+        self.offset = self.base + offset;
         if self.had_linebreak {
             self.line += 1;
             self.col = 1;
@@ -212,6 +235,24 @@ mod test_stream {
         assert_end(&mut stream);
     }
 
+    #[test]
+    fn test_feed_resumes_after_more_data_is_appended() {
+        let buffer = String::from("AB");
+        let mut stream = Stream::new(&buffer);
+        assert_char(&mut stream, 'A', 0, 1, 1);
+        assert_char(&mut stream, 'B', 1, 1, 2);
+        assert_end(&mut stream);
+
+        // A real caller can't grow `buffer` in place while `stream` still
+        // borrows it, so `feed` takes a fresh slice with the same prefix.
+        let grown = format!("{buffer}\nCD");
+        stream.feed(&grown);
+        assert_char(&mut stream, '\n', 2, 1, 3);
+        assert_char(&mut stream, 'C', 3, 2, 1);
+        assert_char(&mut stream, 'D', 4, 2, 2);
+        assert_end(&mut stream);
+    }
+
     fn assert_char(stream: &mut Stream, value: char, offset: usize, line: usize, col: usize) {
         if let Some(c) = stream.next() {
             assert_eq!(c, value);